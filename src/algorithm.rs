@@ -0,0 +1,98 @@
+//! The encryption algorithm used to encrypt an event or key backup.
+
+use serde::{
+    de::{Deserialize, Deserializer},
+    ser::{Serialize, Serializer},
+};
+
+/// An encryption algorithm to be used when encrypting an event, or when encrypting a room key for
+/// server-side backup.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Algorithm {
+    /// Olm version 1 using Curve25519, AES-256, and SHA-256.
+    OlmV1Curve25519AesSha2,
+
+    /// Megolm version 1 using AES-256 and SHA-256.
+    MegolmV1AesSha2,
+
+    /// Server-side Megolm key backups, version 1, using Curve25519, AES-256, and SHA-256.
+    MegolmBackupV1Curve25519AesSha2,
+
+    /// Any algorithm that is not part of the Matrix specification.
+    Custom(String),
+
+    /// Additional variants may be added in the future and will not be considered breaking changes
+    /// to ruma-events.
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+impl Algorithm {
+    fn as_str(&self) -> &str {
+        match self {
+            Self::OlmV1Curve25519AesSha2 => "m.olm.v1.curve25519-aes-sha2",
+            Self::MegolmV1AesSha2 => "m.megolm.v1.aes-sha2",
+            Self::MegolmBackupV1Curve25519AesSha2 => "m.megolm_backup.v1.curve25519-aes-sha2",
+            Self::Custom(algorithm) => algorithm,
+            Self::__Nonexhaustive => {
+                unreachable!("__Nonexhaustive variant should be impossible to obtain.")
+            }
+        }
+    }
+}
+
+impl Serialize for Algorithm {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Algorithm {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let algorithm = String::deserialize(deserializer)?;
+
+        Ok(match algorithm.as_str() {
+            "m.olm.v1.curve25519-aes-sha2" => Self::OlmV1Curve25519AesSha2,
+            "m.megolm.v1.aes-sha2" => Self::MegolmV1AesSha2,
+            "m.megolm_backup.v1.curve25519-aes-sha2" => Self::MegolmBackupV1Curve25519AesSha2,
+            _ => Self::Custom(algorithm),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
+
+    use super::Algorithm;
+
+    #[test]
+    fn serialize_megolm_backup() {
+        assert_eq!(
+            to_json_value(&Algorithm::MegolmBackupV1Curve25519AesSha2).unwrap(),
+            json!("m.megolm_backup.v1.curve25519-aes-sha2")
+        );
+    }
+
+    #[test]
+    fn deserialize_megolm_backup() {
+        assert_eq!(
+            from_json_value::<Algorithm>(json!("m.megolm_backup.v1.curve25519-aes-sha2")).unwrap(),
+            Algorithm::MegolmBackupV1Curve25519AesSha2
+        );
+    }
+
+    #[test]
+    fn deserialize_custom_algorithm() {
+        assert_eq!(
+            from_json_value::<Algorithm>(json!("org.example.custom")).unwrap(),
+            Algorithm::Custom("org.example.custom".to_string())
+        );
+    }
+}
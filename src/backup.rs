@@ -0,0 +1,143 @@
+//! Types for *m.megolm_backup.v1.curve25519-aes-sha2* server-side key backups.
+
+use std::collections::BTreeMap;
+
+use js_int::UInt;
+use serde::{Deserialize, Serialize};
+
+use crate::Algorithm;
+
+/// The algorithm-specific key material, decrypted from a key backup.
+///
+/// This is the cleartext counterpart of [`SessionData`], the encrypted blob actually stored on
+/// the server.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct BackedUpRoomKey {
+    /// The encryption algorithm that the session used.
+    pub algorithm: Algorithm,
+
+    /// Devices which forwarded this session to us (normally empty).
+    pub forwarding_curve25519_key_chain: Vec<String>,
+
+    /// The Ed25519 keys of the devices that sent this session initially, if known.
+    pub sender_claimed_keys: BTreeMap<String, String>,
+
+    /// The key for the session.
+    pub session_key: String,
+}
+
+/// A session's backup, as stored on (and retrieved from) the server.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct KeyBackupData {
+    /// The index of the first message in the session that the key can decrypt.
+    pub first_message_index: UInt,
+
+    /// The number of times this key has been forwarded via key-sharing between devices.
+    pub forwarded_count: UInt,
+
+    /// Whether the device backing up the key verified the device that the key is from.
+    pub is_verified: bool,
+
+    /// The encrypted `BackedUpRoomKey`, encrypted using the backup's public Curve25519 key.
+    pub session_data: SessionData,
+}
+
+/// The encrypted form of a `BackedUpRoomKey`, as uploaded to and downloaded from the server.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct SessionData {
+    /// Unpadded base64-encoded ciphertext, encrypted using the backup's public Curve25519 key.
+    pub ciphertext: String,
+
+    /// Unpadded base64-encoded public half of the ephemeral key.
+    pub ephemeral: String,
+
+    /// Unpadded base64-encoded MAC of the ciphertext.
+    pub mac: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, convert::TryFrom};
+
+    use js_int::UInt;
+    use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
+
+    use super::{BackedUpRoomKey, KeyBackupData, SessionData};
+    use crate::Algorithm;
+
+    #[test]
+    fn serialization() {
+        let mut sender_claimed_keys = BTreeMap::new();
+        sender_claimed_keys.insert("ed25519".to_string(), "ClaimedKey".to_string());
+
+        let key_backup_data = KeyBackupData {
+            first_message_index: UInt::try_from(0u64).unwrap(),
+            forwarded_count: UInt::try_from(0u64).unwrap(),
+            is_verified: true,
+            session_data: SessionData {
+                ciphertext: "Ciphertext".to_string(),
+                ephemeral: "Ephemeral".to_string(),
+                mac: "Mac".to_string(),
+            },
+        };
+
+        let json_data = json!({
+            "first_message_index": 0,
+            "forwarded_count": 0,
+            "is_verified": true,
+            "session_data": {
+                "ciphertext": "Ciphertext",
+                "ephemeral": "Ephemeral",
+                "mac": "Mac"
+            }
+        });
+
+        assert_eq!(to_json_value(&key_backup_data).unwrap(), json_data);
+
+        let backed_up_room_key = BackedUpRoomKey {
+            algorithm: Algorithm::MegolmBackupV1Curve25519AesSha2,
+            forwarding_curve25519_key_chain: vec!["Key1".to_string()],
+            sender_claimed_keys,
+            session_key: "SessionKey".to_string(),
+        };
+
+        let json_data = json!({
+            "algorithm": "m.megolm_backup.v1.curve25519-aes-sha2",
+            "forwarding_curve25519_key_chain": ["Key1"],
+            "sender_claimed_keys": {
+                "ed25519": "ClaimedKey"
+            },
+            "session_key": "SessionKey"
+        });
+
+        assert_eq!(to_json_value(&backed_up_room_key).unwrap(), json_data);
+    }
+
+    #[test]
+    fn deserialization() {
+        let json_data = json!({
+            "algorithm": "m.megolm_backup.v1.curve25519-aes-sha2",
+            "forwarding_curve25519_key_chain": ["Key1"],
+            "sender_claimed_keys": {
+                "ed25519": "ClaimedKey"
+            },
+            "session_key": "SessionKey"
+        });
+
+        let backed_up_room_key = from_json_value::<BackedUpRoomKey>(json_data).unwrap();
+
+        assert_eq!(
+            backed_up_room_key.algorithm,
+            Algorithm::MegolmBackupV1Curve25519AesSha2
+        );
+        assert_eq!(
+            backed_up_room_key.forwarding_curve25519_key_chain,
+            vec!["Key1".to_string()]
+        );
+        assert_eq!(
+            backed_up_room_key.sender_claimed_keys["ed25519"],
+            "ClaimedKey"
+        );
+        assert_eq!(backed_up_room_key.session_key, "SessionKey");
+    }
+}
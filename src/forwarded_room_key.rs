@@ -0,0 +1,105 @@
+//! Types for the *m.forwarded_room_key* event.
+
+use ruma_identifiers::RoomId;
+use serde::{Deserialize, Serialize};
+
+use crate::{Algorithm, EventType, FromRaw};
+
+/// This event type is used to forward keys for end-to-end encryption.
+///
+/// Typically it is encrypted as an *m.room.encrypted* event, then sent as a to-device event.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(tag = "type", rename = "m.forwarded_room_key")]
+pub struct ForwardedRoomKeyEvent {
+    /// The event's content.
+    pub content: ForwardedRoomKeyEventContent,
+}
+
+/// The payload for `ForwardedRoomKeyEvent`.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ForwardedRoomKeyEventContent {
+    /// The encryption algorithm the key in this event is to be used with.
+    pub algorithm: Algorithm,
+
+    /// Chain of Curve25519 keys through which this session was forwarded, via m.forwarded_room_key
+    /// events.
+    pub forwarding_curve25519_key_chain: Vec<String>,
+
+    /// The room where the key is used.
+    pub room_id: RoomId,
+
+    /// The Ed25519 key of the device which initiated the session originally.
+    pub sender_claimed_ed25519_key: String,
+
+    /// The Curve25519 key of the device which initiated the session originally.
+    pub sender_key: String,
+
+    /// The ID of the session that the key is for.
+    pub session_id: String,
+
+    /// The key to be exchanged.
+    pub session_key: String,
+}
+
+impl FromRaw for ForwardedRoomKeyEvent {
+    type Raw = Self;
+
+    fn from_raw(raw: Self) -> Self {
+        raw
+    }
+}
+
+impl FromRaw for ForwardedRoomKeyEventContent {
+    type Raw = Self;
+
+    fn from_raw(raw: Self) -> Self {
+        raw
+    }
+}
+
+impl_event!(
+    ForwardedRoomKeyEvent,
+    ForwardedRoomKeyEventContent,
+    EventType::ForwardedRoomKey
+);
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use ruma_identifiers::RoomId;
+    use serde_json::{json, to_value as to_json_value};
+
+    use super::{ForwardedRoomKeyEvent, ForwardedRoomKeyEventContent};
+    use crate::Algorithm;
+
+    #[test]
+    fn serialization() {
+        let event = ForwardedRoomKeyEvent {
+            content: ForwardedRoomKeyEventContent {
+                algorithm: Algorithm::MegolmV1AesSha2,
+                forwarding_curve25519_key_chain: vec!["Key1".into(), "Key2".into()],
+                room_id: RoomId::try_from("!testroomid:example.org").unwrap(),
+                sender_claimed_ed25519_key: "SenderClaimedEd25519Key".into(),
+                sender_key: "SenderKey".into(),
+                session_id: "SessId".into(),
+                session_key: "SessKey".into(),
+            },
+        };
+
+        let json_data = json!({
+            "type": "m.forwarded_room_key",
+            "content": {
+                "algorithm": "m.megolm.v1.aes-sha2",
+                "forwarding_curve25519_key_chain": ["Key1", "Key2"],
+                "room_id": "!testroomid:example.org",
+                "sender_claimed_ed25519_key": "SenderClaimedEd25519Key",
+                "sender_key": "SenderKey",
+                "session_id": "SessId",
+                "session_key": "SessKey"
+            }
+        });
+
+        assert_eq!(to_json_value(&event).unwrap(), json_data);
+    }
+}
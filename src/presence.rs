@@ -1,45 +1,124 @@
-//! Events within the *m.presence* namespace.
+//! Types for the *m.presence* event.
 
-use core::Event;
+use ruma_identifiers::UserId;
+use serde::{Deserialize, Serialize};
+
+use crate::{EventType, FromRaw};
 
 /// Informs the client of a user's presence state change.
-pub struct Presence<'a> {
-    content: PresenceContent<'a>,
-    event_id: &'a str,
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(tag = "type", rename = "m.presence")]
+pub struct PresenceEvent {
+    /// The event's content.
+    pub content: PresenceContent,
 }
 
-impl<'a> Event<'a, PresenceContent<'a>> for Presence<'a> {
-    fn content(&'a self) -> &'a PresenceContent {
-        &self.content
-    }
+/// The payload of a `PresenceEvent`.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct PresenceContent {
+    /// The current avatar URL for this user.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avatar_url: Option<String>,
 
-    fn event_type(&self) -> &'static str {
-        "m.presence"
-    }
-}
+    /// Whether or not the user is currently active.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currently_active: Option<bool>,
 
-/// The payload of a `Presence` event.
-pub struct PresenceContent<'a> {
-    /// The current avatar URL for this user.
-    avatar_url: Option<&'a str>,
     /// The current display name for this user.
-    displayname: Option<&'a str>,
-    /// The last time since this used performed some action, in milliseconds.
-    last_active_ago: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub displayname: Option<String>,
+
+    /// The last time since this user performed some action, in milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_active_ago: Option<u64>,
+
     /// The presence state for this user.
-    presence: PresenceState,
+    pub presence: PresenceState,
+
+    /// An optional description to accompany the presence.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_msg: Option<String>,
+
+    /// The user whose presence this is.
+    pub user_id: UserId,
 }
 
 /// A description of a user's connectivity and availability for chat.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
 pub enum PresenceState {
     /// Connected to the service and available for chat.
+    #[serde(rename = "free_for_chat")]
     FreeForChat,
+
     /// Connected to the service but not visible to other users.
+    #[serde(rename = "hidden")]
     Hidden,
+
     /// Disconnected from the service.
+    #[serde(rename = "offline")]
     Offline,
+
     /// Connected to the service.
+    #[serde(rename = "online")]
     Online,
+
     /// Connected to the service but not available for chat.
+    #[serde(rename = "unavailable")]
     Unavailable,
 }
+
+impl FromRaw for PresenceEvent {
+    type Raw = Self;
+
+    fn from_raw(raw: Self) -> Self {
+        raw
+    }
+}
+
+impl FromRaw for PresenceContent {
+    type Raw = Self;
+
+    fn from_raw(raw: Self) -> Self {
+        raw
+    }
+}
+
+impl_event!(PresenceEvent, PresenceContent, EventType::Presence);
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use ruma_identifiers::UserId;
+    use serde_json::{json, to_value as to_json_value};
+
+    use super::{PresenceContent, PresenceEvent, PresenceState};
+
+    #[test]
+    fn serialization() {
+        let event = PresenceEvent {
+            content: PresenceContent {
+                avatar_url: None,
+                currently_active: Some(true),
+                displayname: None,
+                last_active_ago: Some(2_478_593),
+                presence: PresenceState::Online,
+                status_msg: Some("Making cupcakes".to_string()),
+                user_id: UserId::try_from("@example:example.org").unwrap(),
+            },
+        };
+
+        let json_data = json!({
+            "type": "m.presence",
+            "content": {
+                "currently_active": true,
+                "last_active_ago": 2_478_593,
+                "presence": "online",
+                "status_msg": "Making cupcakes",
+                "user_id": "@example:example.org"
+            }
+        });
+
+        assert_eq!(to_json_value(&event).unwrap(), json_data);
+    }
+}
@@ -5,8 +5,9 @@ use std::{collections::BTreeMap, time::SystemTime};
 use js_int::UInt;
 use ruma_identifiers::{DeviceId, EventId, RoomId, UserId};
 use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
 
-use crate::{Algorithm, EventType, FromRaw, UnsignedData};
+use crate::{Algorithm, EventJson, EventType, FromRaw, UnsignedData};
 
 /// This event type is used when sending encrypted events.
 ///
@@ -39,20 +40,53 @@ pub struct EncryptedEvent {
 
 /// The payload for `EncryptedEvent`.
 #[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct EncryptedEventContent {
+    /// Algorithm-specific fields.
+    #[serde(flatten)]
+    pub scheme: EncryptedEventScheme,
+
+    /// Information about related events.
+    #[serde(rename = "m.relates_to", skip_serializing_if = "Option::is_none")]
+    pub relates_to: Option<Relation>,
+}
+
+/// The encryption scheme used to encrypt an `EncryptedEventContent`.
+#[derive(Clone, Debug, PartialEq, Serialize)]
 #[serde(untagged)]
-pub enum EncryptedEventContent {
+pub enum EncryptedEventScheme {
     /// An event encrypted with *m.olm.v1.curve25519-aes-sha2*.
     OlmV1Curve25519AesSha2(OlmV1Curve25519AesSha2Content),
 
     /// An event encrypted with *m.megolm.v1.aes-sha2*.
     MegolmV1AesSha2(MegolmV1AesSha2Content),
 
+    /// An event encrypted with an unknown or custom algorithm.
+    Custom(CustomEncryptedEventContent),
+
     /// Additional variants may be added in the future and will not be considered breaking changes
     /// to ruma-events.
     #[doc(hidden)]
     __Nonexhaustive,
 }
 
+/// The payload for `EncryptedEvent` using an unknown or custom algorithm.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct CustomEncryptedEventContent {
+    /// The encryption algorithm used to encrypt this event.
+    pub algorithm: String,
+
+    /// The remaining content of the event.
+    #[serde(flatten)]
+    pub data: BTreeMap<String, JsonValue>,
+}
+
+/// Information about a related event.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Relation {
+    /// The event that this event is related to.
+    pub event_id: EventId,
+}
+
 impl FromRaw for EncryptedEvent {
     type Raw = raw::EncryptedEvent;
 
@@ -72,16 +106,9 @@ impl FromRaw for EncryptedEventContent {
     type Raw = raw::EncryptedEventContent;
 
     fn from_raw(raw: raw::EncryptedEventContent) -> Self {
-        use raw::EncryptedEventContent::*;
-
-        match raw {
-            OlmV1Curve25519AesSha2(content) => {
-                EncryptedEventContent::OlmV1Curve25519AesSha2(content)
-            }
-            MegolmV1AesSha2(content) => EncryptedEventContent::MegolmV1AesSha2(content),
-            __Nonexhaustive => {
-                unreachable!("__Nonexhaustive variant should be impossible to obtain.")
-            }
+        Self {
+            scheme: raw.scheme,
+            relates_to: raw.relates_to,
         }
     }
 }
@@ -93,13 +120,16 @@ impl_room_event!(
 );
 
 pub(crate) mod raw {
-    use std::time::SystemTime;
+    use std::{collections::BTreeMap, time::SystemTime};
 
     use ruma_identifiers::{EventId, RoomId, UserId};
     use serde::{Deserialize, Deserializer};
     use serde_json::{from_value as from_json_value, Value as JsonValue};
 
-    use super::{MegolmV1AesSha2Content, OlmV1Curve25519AesSha2Content};
+    use super::{
+        CustomEncryptedEventContent, EncryptedEventScheme, MegolmV1AesSha2Content,
+        OlmV1Curve25519AesSha2Content, Relation,
+    };
     use crate::{Algorithm, UnsignedData};
 
     /// This event type is used when sending encrypted events.
@@ -131,17 +161,12 @@ pub(crate) mod raw {
 
     /// The payload for `EncryptedEvent`.
     #[derive(Clone, Debug, PartialEq)]
-    pub enum EncryptedEventContent {
-        /// An event encrypted with *m.olm.v1.curve25519-aes-sha2*.
-        OlmV1Curve25519AesSha2(OlmV1Curve25519AesSha2Content),
-
-        /// An event encrypted with *m.megolm.v1.aes-sha2*.
-        MegolmV1AesSha2(MegolmV1AesSha2Content),
+    pub struct EncryptedEventContent {
+        /// Algorithm-specific fields.
+        pub scheme: EncryptedEventScheme,
 
-        /// Additional variants may be added in the future and will not be considered breaking
-        /// changes to ruma-events.
-        #[doc(hidden)]
-        __Nonexhaustive,
+        /// Information about related events.
+        pub relates_to: Option<Relation>,
     }
 
     impl<'de> Deserialize<'de> for EncryptedEventContent {
@@ -163,14 +188,22 @@ pub(crate) mod raw {
                 Err(error) => return Err(D::Error::custom(error.to_string())),
             };
 
-            match method {
+            let relates_to = match value.get("m.relates_to") {
+                Some(value) => match from_json_value::<Relation>(value.clone()) {
+                    Ok(relation) => Some(relation),
+                    Err(error) => return Err(D::Error::custom(error.to_string())),
+                },
+                None => None,
+            };
+
+            let scheme = match method {
                 Algorithm::OlmV1Curve25519AesSha2 => {
                     let content = match from_json_value::<OlmV1Curve25519AesSha2Content>(value) {
                         Ok(content) => content,
                         Err(error) => return Err(D::Error::custom(error.to_string())),
                     };
 
-                    Ok(EncryptedEventContent::OlmV1Curve25519AesSha2(content))
+                    EncryptedEventScheme::OlmV1Curve25519AesSha2(content)
                 }
                 Algorithm::MegolmV1AesSha2 => {
                     let content = match from_json_value::<MegolmV1AesSha2Content>(value) {
@@ -178,15 +211,31 @@ pub(crate) mod raw {
                         Err(error) => return Err(D::Error::custom(error.to_string())),
                     };
 
-                    Ok(EncryptedEventContent::MegolmV1AesSha2(content))
+                    EncryptedEventScheme::MegolmV1AesSha2(content)
                 }
-                Algorithm::Custom(_) => Err(D::Error::custom(
-                    "Custom algorithms are not supported by `EncryptedEventContent`.",
-                )),
-                Algorithm::__Nonexhaustive => Err(D::Error::custom(
-                    "Attempted to deserialize __Nonexhaustive variant.",
-                )),
-            }
+                Algorithm::Custom(algorithm) => {
+                    let mut data = match value {
+                        JsonValue::Object(map) => map.into_iter().collect::<BTreeMap<_, _>>(),
+                        _ => return Err(D::Error::custom("expected an object")),
+                    };
+                    data.remove("algorithm");
+                    data.remove("m.relates_to");
+
+                    EncryptedEventScheme::Custom(CustomEncryptedEventContent { algorithm, data })
+                }
+                Algorithm::MegolmBackupV1Curve25519AesSha2 => {
+                    return Err(D::Error::custom(
+                        "The key backup algorithm cannot be used to encrypt an event.",
+                    ))
+                }
+                Algorithm::__Nonexhaustive => {
+                    return Err(D::Error::custom(
+                        "Attempted to deserialize __Nonexhaustive variant.",
+                    ))
+                }
+            };
+
+            Ok(Self { scheme, relates_to })
         }
     }
 }
@@ -236,24 +285,83 @@ pub struct MegolmV1AesSha2Content {
     pub session_id: String,
 }
 
+/// The result of successfully decrypting an `EncryptedEvent`.
+#[derive(Clone, Debug)]
+pub struct DecryptedEvent<C> {
+    /// The decrypted event content.
+    pub event: EventJson<C>,
+
+    /// The encryption info attached to the decrypted event.
+    pub encryption_info: EncryptionInfo,
+}
+
+/// The encryption info attached to a successfully decrypted event, for surfacing device-trust
+/// metadata to the caller.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EncryptionInfo {
+    /// The user that sent the encrypted event.
+    pub sender: UserId,
+
+    /// The device that sent the encrypted event.
+    pub sender_device: DeviceId,
+
+    /// Information about the algorithm used to encrypt the event.
+    pub algorithm_info: AlgorithmInfo,
+
+    /// The verification state of the device that sent the encrypted event.
+    pub verification_state: VerificationState,
+}
+
+/// Algorithm-specific information about the encryption used for a decrypted event.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AlgorithmInfo {
+    /// Information about the megolm session used to encrypt the event.
+    MegolmV1AesSha2 {
+        /// The Curve25519 key of the sending device.
+        curve25519_key: String,
+
+        /// The Ed25519 keys claimed by the devices that created this megolm session, keyed by
+        /// their Curve25519 identity key.
+        sender_claimed_keys: BTreeMap<String, String>,
+    },
+}
+
+/// Whether the device that sent an encrypted event has been verified.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VerificationState {
+    /// The sending device has been verified.
+    Verified,
+
+    /// The sending device has not been verified.
+    Unverified,
+}
+
 #[cfg(test)]
 mod tests {
+    use std::{collections::BTreeMap, convert::TryFrom};
+
     use matches::assert_matches;
+    use ruma_identifiers::{DeviceId, UserId};
     use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
 
-    use super::{Algorithm, EncryptedEventContent, MegolmV1AesSha2Content};
+    use super::{
+        Algorithm, AlgorithmInfo, DecryptedEvent, EncryptedEventContent, EncryptedEventScheme,
+        EncryptionInfo, MegolmV1AesSha2Content, Relation, VerificationState,
+    };
     use crate::EventJson;
 
     #[test]
     fn serialization() {
-        let key_verification_start_content =
-            EncryptedEventContent::MegolmV1AesSha2(MegolmV1AesSha2Content {
+        let key_verification_start_content = EncryptedEventContent {
+            scheme: EncryptedEventScheme::MegolmV1AesSha2(MegolmV1AesSha2Content {
                 algorithm: Algorithm::MegolmV1AesSha2,
                 ciphertext: "ciphertext".to_string(),
                 sender_key: "sender_key".to_string(),
                 device_id: "device_id".to_string(),
                 session_id: "session_id".to_string(),
-            });
+            }),
+            relates_to: None,
+        };
 
         let json_data = json!({
             "algorithm": "m.megolm.v1.aes-sha2",
@@ -284,13 +392,16 @@ mod tests {
                 .unwrap()
                 .deserialize()
                 .unwrap(),
-            EncryptedEventContent::MegolmV1AesSha2(MegolmV1AesSha2Content {
-                algorithm: Algorithm::MegolmV1AesSha2,
-                ciphertext,
-                sender_key,
-                device_id,
-                session_id,
-            }) if ciphertext == "ciphertext"
+            EncryptedEventContent {
+                scheme: EncryptedEventScheme::MegolmV1AesSha2(MegolmV1AesSha2Content {
+                    algorithm: Algorithm::MegolmV1AesSha2,
+                    ciphertext,
+                    sender_key,
+                    device_id,
+                    session_id,
+                }),
+                relates_to: None,
+            } if ciphertext == "ciphertext"
                 && sender_key == "sender_key"
                 && device_id == "device_id"
                 && session_id == "session_id"
@@ -314,8 +425,8 @@ mod tests {
             .deserialize()
             .unwrap();
 
-        match content {
-            EncryptedEventContent::OlmV1Curve25519AesSha2(c) => {
+        match content.scheme {
+            EncryptedEventScheme::OlmV1Curve25519AesSha2(c) => {
                 assert_eq!(c.algorithm, Algorithm::OlmV1Curve25519AesSha2);
                 assert_eq!(c.sender_key, "test_key");
                 assert_eq!(c.ciphertext.len(), 1);
@@ -324,6 +435,54 @@ mod tests {
             }
             _ => panic!("Wrong content type, expected a OlmV1 content"),
         }
+        assert_matches!(content.relates_to, None);
+    }
+
+    #[test]
+    fn deserialization_with_relates_to() {
+        let json_data = json!({
+            "algorithm": "m.megolm.v1.aes-sha2",
+            "ciphertext": "ciphertext",
+            "sender_key": "sender_key",
+            "device_id": "device_id",
+            "session_id": "session_id",
+            "m.relates_to": {
+                "event_id": "$related_event_id:example.com"
+            }
+        });
+
+        let content = from_json_value::<EventJson<EncryptedEventContent>>(json_data)
+            .unwrap()
+            .deserialize()
+            .unwrap();
+
+        assert_matches!(
+            content.relates_to,
+            Some(Relation { event_id }) if event_id == "$related_event_id:example.com"
+        );
+    }
+
+    #[test]
+    fn deserialization_custom() {
+        let json_data = json!({
+            "algorithm": "org.example.custom",
+            "some_field": "some_value"
+        });
+
+        let content = from_json_value::<EventJson<EncryptedEventContent>>(json_data.clone())
+            .unwrap()
+            .deserialize()
+            .unwrap();
+
+        match &content.scheme {
+            EncryptedEventScheme::Custom(c) => {
+                assert_eq!(c.algorithm, "org.example.custom");
+                assert_eq!(c.data["some_field"], "some_value");
+            }
+            _ => panic!("Wrong content type, expected a Custom content"),
+        }
+
+        assert_eq!(to_json_value(&content).unwrap(), json_data);
     }
 
     #[test]
@@ -335,4 +494,77 @@ mod tests {
         .deserialize()
         .is_err());
     }
+
+    #[test]
+    fn encryption_info_construction() {
+        let mut sender_claimed_keys = BTreeMap::new();
+        sender_claimed_keys.insert("curve25519".to_string(), "ClaimedKey".to_string());
+
+        let sender_device: DeviceId = "DEVICEID".to_string();
+
+        let encryption_info = EncryptionInfo {
+            sender: UserId::try_from("@example:example.org").unwrap(),
+            sender_device: sender_device.clone(),
+            algorithm_info: AlgorithmInfo::MegolmV1AesSha2 {
+                curve25519_key: "SenderKey".to_string(),
+                sender_claimed_keys: sender_claimed_keys.clone(),
+            },
+            verification_state: VerificationState::Verified,
+        };
+
+        assert_eq!(
+            encryption_info.sender,
+            UserId::try_from("@example:example.org").unwrap()
+        );
+        assert_eq!(encryption_info.sender_device, sender_device);
+        assert_eq!(
+            encryption_info.verification_state,
+            VerificationState::Verified
+        );
+        assert_matches!(
+            encryption_info.algorithm_info,
+            AlgorithmInfo::MegolmV1AesSha2 { curve25519_key, sender_claimed_keys: keys }
+                if curve25519_key == "SenderKey" && keys == sender_claimed_keys
+        );
+    }
+
+    #[test]
+    fn decrypted_event_construction() {
+        let json_data = json!({
+            "algorithm": "m.megolm.v1.aes-sha2",
+            "ciphertext": "ciphertext",
+            "sender_key": "sender_key",
+            "device_id": "device_id",
+            "session_id": "session_id"
+        });
+
+        let event: EventJson<EncryptedEventContent> = from_json_value(json_data).unwrap();
+
+        let encryption_info = EncryptionInfo {
+            sender: UserId::try_from("@example:example.org").unwrap(),
+            sender_device: "DEVICEID".to_string(),
+            algorithm_info: AlgorithmInfo::MegolmV1AesSha2 {
+                curve25519_key: "SenderKey".to_string(),
+                sender_claimed_keys: BTreeMap::new(),
+            },
+            verification_state: VerificationState::Unverified,
+        };
+
+        let decrypted_event = DecryptedEvent {
+            event,
+            encryption_info: encryption_info.clone(),
+        };
+
+        assert_matches!(
+            decrypted_event.event.deserialize().unwrap(),
+            EncryptedEventContent {
+                scheme: EncryptedEventScheme::MegolmV1AesSha2(MegolmV1AesSha2Content {
+                    session_id,
+                    ..
+                }),
+                ..
+            } if session_id == "session_id"
+        );
+        assert_eq!(decrypted_event.encryption_info, encryption_info);
+    }
 }
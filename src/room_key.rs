@@ -0,0 +1,85 @@
+//! Types for the *m.room_key* event.
+
+use ruma_identifiers::RoomId;
+use serde::{Deserialize, Serialize};
+
+use crate::{Algorithm, EventType, FromRaw};
+
+/// This event type is used to exchange keys for end-to-end encryption.
+///
+/// Typically it is encrypted as an *m.room.encrypted* event, then sent as a to-device event.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(tag = "type", rename = "m.room_key")]
+pub struct RoomKeyEvent {
+    /// The event's content.
+    pub content: RoomKeyEventContent,
+}
+
+/// The payload for `RoomKeyEvent`.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct RoomKeyEventContent {
+    /// The encryption algorithm the key in this event is to be used with.
+    pub algorithm: Algorithm,
+
+    /// The room where the key is used.
+    pub room_id: RoomId,
+
+    /// The ID of the session that the key is for.
+    pub session_id: String,
+
+    /// The key to be exchanged.
+    pub session_key: String,
+}
+
+impl FromRaw for RoomKeyEvent {
+    type Raw = Self;
+
+    fn from_raw(raw: Self) -> Self {
+        raw
+    }
+}
+
+impl FromRaw for RoomKeyEventContent {
+    type Raw = Self;
+
+    fn from_raw(raw: Self) -> Self {
+        raw
+    }
+}
+
+impl_event!(RoomKeyEvent, RoomKeyEventContent, EventType::RoomKey);
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use ruma_identifiers::RoomId;
+    use serde_json::{json, to_value as to_json_value};
+
+    use super::{RoomKeyEvent, RoomKeyEventContent};
+    use crate::Algorithm;
+
+    #[test]
+    fn serialization() {
+        let event = RoomKeyEvent {
+            content: RoomKeyEventContent {
+                algorithm: Algorithm::MegolmV1AesSha2,
+                room_id: RoomId::try_from("!testroomid:example.org").unwrap(),
+                session_id: "SessId".into(),
+                session_key: "SessKey".into(),
+            },
+        };
+
+        let json_data = json!({
+            "type": "m.room_key",
+            "content": {
+                "algorithm": "m.megolm.v1.aes-sha2",
+                "room_id": "!testroomid:example.org",
+                "session_id": "SessId",
+                "session_key": "SessKey"
+            }
+        });
+
+        assert_eq!(to_json_value(&event).unwrap(), json_data);
+    }
+}